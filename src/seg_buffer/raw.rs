@@ -1,13 +1,31 @@
 use std::cell::UnsafeCell;
 use std::cmp::min;
+use std::io;
+#[cfg(feature = "mpmc")]
+use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+#[cfg(feature = "mpmc")]
+use std::sync::Arc;
 use std::{iter, ptr};
 
 /// Capacity of the first segment in the buffer.
 const STARTING_SIZE: usize = 64;
 /// Max capacity of a segment in the buffer.
 const MAX_SIZE: usize = 262_144;
+/// Default growth factor applied to a segment's capacity each time the
+/// buffer grows into a new one.
+const DEFAULT_GROWTH_FACTOR: usize = 2;
+/// Default number of emptied segments kept around for reuse by
+/// [`RawBufferBuilder`]-configured buffers.
+const DEFAULT_MAX_FREE_SEGMENTS: usize = 4;
+
+/// Slot has not yet been written by the producer that claimed it.
+#[cfg(feature = "mpmc")]
+const SLOT_EMPTY: usize = 0;
+/// The value has been written and is ready to be read.
+#[cfg(feature = "mpmc")]
+const SLOT_WRITTEN: usize = 2;
 
 /// A segment in the buffer.
 struct Segment<T> {
@@ -33,6 +51,215 @@ struct Segment<T> {
     ///
     /// This array has length `self.capacity`.
     array: Vec<UnsafeCell<MaybeUninit<T>>>,
+    /// Per-slot publication state, used by the concurrent `pop_concurrent`
+    /// to tell a claimed-but-not-yet-written slot apart from one that
+    /// is safe to read. Indexing matches `array`.
+    #[cfg(feature = "mpmc")]
+    states: Vec<AtomicUsize>,
+    /// Count of slots in this segment, claimed or not, that have not yet
+    /// been consumed by a concurrent `pop_concurrent`.
+    /// Starts at `capacity` and is decremented once per slot consumed;
+    /// the segment can only be freed once this reaches zero, since
+    /// only then is every slot guaranteed to have been read out.
+    #[cfg(feature = "mpmc")]
+    live: AtomicUsize,
+    /// Link pointer used only while this segment is parked on
+    /// [`RawBuffer::retired_head`] after being fully drained by
+    /// `pop_concurrent`. Kept separate from `next` (rather than reusing
+    /// it) because a stalled `pop_concurrent` call can still read `next`
+    /// on this exact segment after it's been retired; overwriting it
+    /// here would send that call down the wrong chain.
+    #[cfg(feature = "mpmc")]
+    retired_next: AtomicPtr<Segment<T>>,
+}
+
+impl<T> Segment<T> {
+    /// Marks `index` as written, making it visible to a concurrent
+    /// `pop_concurrent`. Outside the `mpmc` feature there is no per-slot
+    /// state to update, so this is a no-op.
+    #[cfg(feature = "mpmc")]
+    fn publish(&self, index: usize) {
+        self.states[index].store(SLOT_WRITTEN, Ordering::Release);
+    }
+
+    #[cfg(not(feature = "mpmc"))]
+    fn publish(&self, _index: usize) {}
+
+    /// Resets a fully-drained segment so it can be handed back out by
+    /// [`RawBuffer::acquire_segment`] instead of being freed.
+    fn reset(&mut self) {
+        *self.front.get_mut() = 0;
+        *self.back.get_mut() = 0;
+        self.reset_mpmc_state();
+    }
+
+    #[cfg(feature = "mpmc")]
+    fn reset_mpmc_state(&mut self) {
+        for state in &mut self.states {
+            *state.get_mut() = SLOT_EMPTY;
+        }
+        *self.live.get_mut() = self.capacity;
+    }
+
+    #[cfg(not(feature = "mpmc"))]
+    fn reset_mpmc_state(&mut self) {}
+}
+
+/// A bounded, lock-free stack of retired segments available for reuse.
+///
+/// Pushing onto a full free list simply fails (the caller is expected
+/// to free the segment itself instead), and the list reuses each
+/// segment's own `next` pointer as link storage since a parked segment
+/// is by definition detached from the buffer's live segment chain.
+struct FreeList<T> {
+    head: AtomicPtr<Segment<T>>,
+    len: AtomicUsize,
+    cap: usize,
+}
+
+impl<T> FreeList<T> {
+    fn new(cap: usize) -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            len: AtomicUsize::new(0),
+            cap,
+        }
+    }
+
+    /// Attempts to park `segment` on the free list, returning `false`
+    /// (without taking ownership) if the list is already at capacity.
+    fn push(&self, segment: *mut Segment<T>) -> bool {
+        loop {
+            let len = self.len.load(Ordering::Relaxed);
+            if len >= self.cap {
+                return false;
+            }
+            if self.len.compare_and_swap(len, len + 1, Ordering::AcqRel) == len {
+                break;
+            }
+        }
+
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            unsafe { (*segment).next.store(head, Ordering::Relaxed) };
+            if self.head.compare_and_swap(head, segment, Ordering::AcqRel) == head {
+                return true;
+            }
+        }
+    }
+
+    /// Takes a segment off the free list, if any is available.
+    fn pop(&self) -> Option<*mut Segment<T>> {
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            if head.is_null() {
+                return None;
+            }
+            let next = unsafe { (*head).next.load(Ordering::Acquire) };
+            if self.head.compare_and_swap(head, next, Ordering::AcqRel) == head {
+                self.len.fetch_sub(1, Ordering::AcqRel);
+                unsafe { (*head).next.store(ptr::null_mut(), Ordering::Relaxed) };
+                return Some(head);
+            }
+        }
+    }
+}
+
+impl<T> Drop for FreeList<T> {
+    fn drop(&mut self) {
+        while let Some(segment) = self.pop() {
+            unsafe { drop(Box::from_raw(segment)) };
+        }
+    }
+}
+
+/// Builds a [`RawBuffer`] with a non-default growth policy.
+///
+/// The buffer's first segment has capacity `starting_capacity`; each
+/// time the buffer needs to grow, the new segment's capacity is the
+/// previous head's capacity times `growth_factor`, clamped to
+/// `max_capacity` (a segment reused from the free list may be larger,
+/// never smaller). Up to `max_free_segments` emptied segments are kept
+/// in a free list for reuse instead of being dropped, so steady
+/// push/pop churn doesn't keep reallocating; any more than that are
+/// freed immediately, so memory tracks the working set rather than the
+/// buffer's historical peak.
+pub struct RawBufferBuilder {
+    starting_capacity: usize,
+    max_capacity: usize,
+    growth_factor: usize,
+    max_free_segments: usize,
+}
+
+impl Default for RawBufferBuilder {
+    fn default() -> Self {
+        Self {
+            starting_capacity: STARTING_SIZE,
+            max_capacity: MAX_SIZE,
+            growth_factor: DEFAULT_GROWTH_FACTOR,
+            max_free_segments: DEFAULT_MAX_FREE_SEGMENTS,
+        }
+    }
+}
+
+impl RawBufferBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the capacity of the first segment. Defaults to `64`.
+    pub fn starting_capacity(mut self, starting_capacity: usize) -> Self {
+        self.starting_capacity = starting_capacity;
+        self
+    }
+
+    /// Sets the largest capacity a single segment may grow to.
+    /// Defaults to `262_144`.
+    pub fn max_capacity(mut self, max_capacity: usize) -> Self {
+        self.max_capacity = max_capacity;
+        self
+    }
+
+    /// Sets the factor applied to a segment's capacity to compute the
+    /// next segment's capacity. Defaults to `2`.
+    pub fn growth_factor(mut self, growth_factor: usize) -> Self {
+        self.growth_factor = growth_factor;
+        self
+    }
+
+    /// Sets how many emptied segments are retained for reuse. Defaults
+    /// to `4`; pass `0` to free every emptied segment immediately.
+    pub fn max_free_segments(mut self, max_free_segments: usize) -> Self {
+        self.max_free_segments = max_free_segments;
+        self
+    }
+
+    pub fn build<T>(self) -> RawBuffer<T> {
+        assert!(
+            self.starting_capacity > 0,
+            "starting_capacity must be greater than 0"
+        );
+        assert!(
+            self.growth_factor >= 1,
+            "growth_factor must be at least 1"
+        );
+        assert!(self.max_capacity > 0, "max_capacity must be greater than 0");
+        assert!(
+            self.max_capacity >= self.starting_capacity,
+            "max_capacity must be at least starting_capacity"
+        );
+
+        let head = new_segment(self.starting_capacity);
+        RawBuffer {
+            head: AtomicPtr::new(head),
+            tail: AtomicPtr::new(head),
+            max_capacity: self.max_capacity,
+            growth_factor: self.growth_factor,
+            free_list: FreeList::new(self.max_free_segments),
+            #[cfg(feature = "mpmc")]
+            retired_head: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
 }
 
 impl<T> Drop for Segment<T> {
@@ -62,17 +289,91 @@ pub struct RawBuffer<T> {
     ///
     /// This value must never be null.
     tail: AtomicPtr<Segment<T>>,
+    /// Largest capacity a single segment may grow to.
+    max_capacity: usize,
+    /// Factor applied to a segment's capacity to compute the next
+    /// segment's capacity when the buffer grows.
+    growth_factor: usize,
+    /// Emptied segments retained for reuse instead of being freed.
+    free_list: FreeList<T>,
+    /// Head of a lock-free stack of segments retired by `pop_concurrent`
+    /// once fully drained (linked through `Segment::retired_next`), held
+    /// here instead of being freed immediately.
+    ///
+    /// A `pop_concurrent` caller loads `tail` and dereferences the
+    /// segment it points to *before* claiming a slot in it. If that
+    /// thread stalls right there, another consumer can finish draining
+    /// the same segment out from under it and -- if it were freed as
+    /// soon as its last slot is read -- the stalled thread would
+    /// dereference freed memory next. A proper fix needs epoch-based
+    /// reclamation or hazard pointers (as crossbeam's `SegQueue` uses);
+    /// short of that, the only sound option is to never actually free a
+    /// retired segment while a stale `tail` read could still be in
+    /// flight on it. So retirement just parks the segment on this stack,
+    /// and it's only drained and freed once the whole buffer is dropped,
+    /// by which point no `pop_concurrent` call can still be holding a
+    /// pointer into it. This trades bounded extra memory (retained until
+    /// `Drop`) for soundness. Only ever pushed to, and only drained with
+    /// exclusive (`&mut self`) access in `Drop`, so there's no ABA
+    /// concern the way there would be for a stack that's also popped
+    /// concurrently.
+    #[cfg(feature = "mpmc")]
+    retired_head: AtomicPtr<Segment<T>>,
 }
 
 impl<T> RawBuffer<T> {
     pub fn new() -> Self {
-        let head = new_segment(STARTING_SIZE);
-        Self {
-            head: AtomicPtr::new(head),
-            tail: AtomicPtr::new(head),
+        RawBufferBuilder::default().build()
+    }
+
+    /// Returns a segment with at least the given capacity, reusing one
+    /// from the free list if available instead of allocating.
+    fn acquire_segment(&self, capacity: usize) -> *mut Segment<T> {
+        match self.free_list.pop() {
+            Some(segment) if unsafe { (*segment).capacity } >= capacity => {
+                unsafe { (&mut *segment).reset() };
+                segment
+            }
+            Some(segment) => {
+                // Too small for this request; free it and allocate anew.
+                unsafe { drop(Box::from_raw(segment)) };
+                new_segment(capacity)
+            }
+            None => new_segment(capacity),
         }
     }
 
+    /// Splits the buffer into a cloneable [`Producer`] and a single
+    /// [`Consumer`] that may run concurrently with each other -- any
+    /// number of `Producer`s may push at once while the one `Consumer`
+    /// pops, all without `unsafe` at the call site. The backing buffer is
+    /// freed once every handle has been dropped.
+    ///
+    /// Requires the `mpmc` feature: `Consumer` pops through
+    /// [`pop_concurrent`](RawBuffer::pop_concurrent), the only pop path
+    /// that synchronizes against a concurrently-running `push` rather
+    /// than assuming exclusive access to the buffer's tail-side state.
+    ///
+    /// `T: Send` is required because values pushed on one thread are
+    /// read back on another.
+    #[cfg(feature = "mpmc")]
+    pub fn split(self) -> (Producer<T>, Consumer<T>)
+    where
+        T: Send,
+    {
+        let buffer = Arc::new(self);
+        (
+            Producer {
+                buffer: Arc::clone(&buffer),
+                marker: PhantomData,
+            },
+            Consumer {
+                buffer,
+                marker: PhantomData,
+            },
+        )
+    }
+
     /// Pushes a value onto the buffer.
     ///
     /// # Safety
@@ -99,8 +400,9 @@ impl<T> RawBuffer<T> {
                     self.head
                         .compare_and_swap(head as *mut _, next, Ordering::AcqRel);
                 } else {
-                    // Allocate new segment.
-                    let new_segment = new_segment(min(MAX_SIZE, head.capacity * 2));
+                    // Allocate (or reuse from the free list) a new segment.
+                    let new_segment =
+                        self.acquire_segment(min(self.max_capacity, head.capacity * self.growth_factor));
 
                     self.append_segment(new_segment);
                 }
@@ -111,6 +413,11 @@ impl<T> RawBuffer<T> {
         let ptr = (&mut *segment.array[index].get()).as_mut_ptr();
 
         ptr::write(ptr, value);
+
+        // Publish the write so a concurrent `pop_concurrent` spinning on this
+        // slot observes the value rather than uninitialized memory.
+        // No-op outside the `mpmc` feature.
+        segment.publish(index);
     }
 
     /// Removes a value from the start of the buffer.
@@ -131,14 +438,20 @@ impl<T> RawBuffer<T> {
             }
 
             if index >= segment.capacity {
-                *segment.back.get_mut() = 0;
-                *segment.front.get_mut() = 0;
                 if *self.head.get_mut() == segment as *mut _ {
+                    *segment.back.get_mut() = 0;
+                    *segment.front.get_mut() = 0;
                     return None;
                 } else {
                     *self.tail.get_mut() = *segment.next.get_mut();
                     *segment.next.get_mut() = ptr::null_mut();
-                    self.append_segment(segment);
+                    segment.reset();
+                    let segment: *mut Segment<T> = segment;
+                    if !self.free_list.push(segment) {
+                        // Free list is at capacity; drop the segment
+                        // instead of growing memory use without bound.
+                        unsafe { drop(Box::from_raw(segment)) };
+                    }
                 }
             } else {
                 break (segment, index);
@@ -174,6 +487,102 @@ impl<T> RawBuffer<T> {
         }
     }
 
+    /// Concurrently removes a value from the start of the buffer.
+    ///
+    /// Unlike [`RawBuffer::pop`], this may be called from any number of
+    /// threads at once, including while other threads call `push`,
+    /// turning the buffer into an unbounded MPMC queue. Requires the
+    /// `mpmc` feature; the plain `&mut self` `pop` remains the
+    /// zero-overhead single-consumer path.
+    ///
+    /// # Safety
+    /// `iter`/`par_iter` may not run concurrently with this function,
+    /// since they assume exclusive access to the tail.
+    #[cfg(feature = "mpmc")]
+    pub unsafe fn pop_concurrent(&self) -> Option<T> {
+        'outer: loop {
+            let tail_ptr = self.tail.load(Ordering::Acquire);
+            let segment = &*tail_ptr;
+
+            // Unlike the single-consumer `pop`, we can't just `fetch_add`
+            // `back` unconditionally: if the segment turns out to be empty
+            // at that index we'd permanently strand the claimed slot (it
+            // would never be revisited, losing or leaking whatever is
+            // eventually pushed there). So `back` is only ever advanced via
+            // a CAS that is conditioned on the slot already being claimed by
+            // a producer, and we retry against a fresh `front` otherwise.
+            let mut index = segment.back.load(Ordering::Acquire);
+            loop {
+                if index >= segment.capacity {
+                    // This segment is fully claimed. Move on to the next one
+                    // if it exists; otherwise nothing is left to read.
+                    let next = segment.next.load(Ordering::Acquire);
+                    if next.is_null() {
+                        return None;
+                    }
+                    self.tail.compare_and_swap(tail_ptr, next, Ordering::AcqRel);
+                    continue 'outer;
+                }
+
+                if index >= segment.front.load(Ordering::Acquire) {
+                    // Nothing has been pushed this far yet. Leave `back`
+                    // untouched so the slot can still be claimed once a
+                    // producer publishes it.
+                    return None;
+                }
+
+                match segment.back.compare_exchange_weak(
+                    index,
+                    index + 1,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => break,
+                    Err(actual) => {
+                        index = actual;
+                        continue;
+                    }
+                }
+            }
+
+            // Spin until the producer that claimed `index` publishes it.
+            while segment.states[index].load(Ordering::Acquire) != SLOT_WRITTEN {
+                std::hint::spin_loop();
+            }
+
+            let value = ptr::read((&*segment.array[index].get()).as_ptr());
+            self.retire_slot(tail_ptr, segment);
+            return Some(value);
+        }
+    }
+
+    /// Accounts for a slot in `segment` having been fully consumed, and
+    /// parks the segment on `retired_head` once every one of its slots
+    /// has been -- see that field's doc comment for why it isn't freed
+    /// immediately.
+    #[cfg(feature = "mpmc")]
+    unsafe fn retire_slot(&self, segment_ptr: *const Segment<T>, segment: &Segment<T>) {
+        if segment.live.fetch_sub(1, Ordering::AcqRel) != 1 {
+            return;
+        }
+        if self.tail.load(Ordering::Acquire) as *const _ == segment_ptr {
+            return;
+        }
+
+        let segment_ptr = segment_ptr as *mut Segment<T>;
+        loop {
+            let head = self.retired_head.load(Ordering::Acquire);
+            segment.retired_next.store(head, Ordering::Relaxed);
+            if self
+                .retired_head
+                .compare_exchange_weak(head, segment_ptr, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                break;
+            }
+        }
+    }
+
     unsafe fn append_segment(&self, segment: *mut Segment<T>) {
         // Traverse to the end of the list and add the new segment.
         let mut head = self.head.load(Ordering::Acquire);
@@ -189,6 +598,132 @@ impl<T> RawBuffer<T> {
     }
 }
 
+impl<T: Copy> RawBuffer<T> {
+    /// Pushes every element of `src` onto the buffer in one shot.
+    ///
+    /// This is specialized for `T: Copy` so each contiguous run within a
+    /// segment can be moved with a single `copy_nonoverlapping` instead
+    /// of looping element by element through [`RawBuffer::push`].
+    ///
+    /// # Safety
+    /// Only other calls to `push`/`push_slice` may execute concurrently.
+    pub unsafe fn push_slice(&self, src: &[T]) {
+        let mut src = src;
+
+        while !src.is_empty() {
+            // Reserve a contiguous run of indices in the current head
+            // segment, same as `push`'s position-claiming loop.
+            let (segment, start, len) = loop {
+                let head = &mut *self.head.load(Ordering::Acquire);
+
+                let position = head.front.fetch_add(src.len(), Ordering::AcqRel);
+
+                if position < head.capacity {
+                    // Only `len` of the claimed slots may fit; the rest
+                    // stay claimed past `capacity`, leaving `front` above
+                    // `capacity` to mark the segment fully written.
+                    let len = min(src.len(), head.capacity - position);
+                    break (head, position, len);
+                } else {
+                    // We claimed past the end of the segment; fall through
+                    // to the same segment-advance logic as `push`.
+                    let next = head.next.load(Ordering::Acquire);
+                    if !next.is_null() {
+                        self.head
+                            .compare_and_swap(head as *mut _, next, Ordering::AcqRel);
+                    } else {
+                        let new_segment =
+                            self.acquire_segment(min(self.max_capacity, head.capacity * self.growth_factor));
+                        self.append_segment(new_segment);
+                    }
+                }
+            };
+
+            let dst = (&mut *segment.array[start].get()).as_mut_ptr();
+            ptr::copy_nonoverlapping(src.as_ptr(), dst, len);
+            for i in start..start + len {
+                segment.publish(i);
+            }
+
+            src = &src[len..];
+        }
+    }
+
+    /// Pops up to `dst.len()` elements off the front of the buffer into
+    /// `dst`, returning the number of elements actually moved.
+    ///
+    /// This mirrors `push_slice`, copying the largest contiguous run
+    /// available in the tail segment in one `copy_nonoverlapping` before
+    /// advancing to the next segment for any remainder.
+    ///
+    /// # Safety
+    /// Neither push operations or other pop operations may not run in parallel with this function.
+    pub unsafe fn pop_slice(&mut self, dst: &mut [T]) -> usize {
+        let mut moved = 0;
+
+        while moved < dst.len() {
+            let segment = &mut **self.tail.get_mut();
+
+            let back = *segment.back.get_mut();
+            let front = min(*segment.front.get_mut(), segment.capacity);
+
+            if back >= front {
+                if back >= segment.capacity && *self.head.get_mut() != segment as *mut _ {
+                    // Tail segment is fully drained and not the head;
+                    // free list it (or drop it) and continue with the
+                    // next one.
+                    *self.tail.get_mut() = *segment.next.get_mut();
+                    *segment.next.get_mut() = ptr::null_mut();
+                    segment.reset();
+                    let segment: *mut Segment<T> = segment;
+                    if !self.free_list.push(segment) {
+                        unsafe { drop(Box::from_raw(segment)) };
+                    }
+                    continue;
+                }
+                // Nothing left to read anywhere.
+                break;
+            }
+
+            let available = min(front, segment.capacity) - back;
+            let len = min(available, dst.len() - moved);
+
+            let src = (&*segment.array[back].get()).as_ptr();
+            ptr::copy_nonoverlapping(src, dst[moved..].as_mut_ptr(), len);
+
+            *segment.back.get_mut() += len;
+            moved += len;
+        }
+
+        moved
+    }
+}
+
+impl io::Write for RawBuffer<u8> {
+    /// Pushes as many bytes of `buf` onto the buffer as will fit,
+    /// backed by [`RawBuffer::push_slice`]. Never blocks and never
+    /// fails to make progress: the buffer is unbounded, so this always
+    /// writes the whole slice.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        unsafe { self.push_slice(buf) };
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Read for RawBuffer<u8> {
+    /// Drains available bytes into `buf`, backed by
+    /// [`RawBuffer::pop_slice`]. Never blocks: if the buffer is
+    /// currently empty this returns `Ok(0)` rather than waiting for a
+    /// producer.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(unsafe { self.pop_slice(buf) })
+    }
+}
+
 impl<T> Drop for RawBuffer<T> {
     fn drop(&mut self) {
         let mut tail = *self.tail.get_mut();
@@ -200,6 +735,22 @@ impl<T> Drop for RawBuffer<T> {
                 tail = temp;
             }
         }
+
+        // No `pop_concurrent` call can still be holding a pointer into a
+        // retired segment once we're here, since dropping the buffer
+        // requires every `Producer`/`Consumer` (and thus every in-flight
+        // call into it) to already be gone.
+        #[cfg(feature = "mpmc")]
+        {
+            let mut retired = *self.retired_head.get_mut();
+            while !retired.is_null() {
+                unsafe {
+                    let next = *(&mut *retired).retired_next.get_mut();
+                    drop(Box::from_raw(retired));
+                    retired = next;
+                }
+            }
+        }
     }
 }
 
@@ -212,6 +763,14 @@ fn new_segment<T>(capacity: usize) -> *mut Segment<T> {
         array: iter::repeat_with(|| UnsafeCell::new(MaybeUninit::uninit()))
             .take(capacity)
             .collect(),
+        #[cfg(feature = "mpmc")]
+        states: iter::repeat_with(|| AtomicUsize::new(SLOT_EMPTY))
+            .take(capacity)
+            .collect(),
+        #[cfg(feature = "mpmc")]
+        live: AtomicUsize::new(capacity),
+        #[cfg(feature = "mpmc")]
+        retired_next: AtomicPtr::new(ptr::null_mut()),
     });
 
     Box::into_raw(boxed)
@@ -250,8 +809,13 @@ pub use self::rayon::*;
 #[cfg(feature = "rayon")]
 mod rayon {
     use crate::seg_buffer::raw::{RawBuffer, RawIter, Segment};
-    use rayon::iter::plumbing::{Consumer, Folder, UnindexedConsumer, UnindexedProducer};
-    use rayon::iter::{plumbing, ParallelIterator};
+    use rayon::iter::plumbing::{
+        Consumer, Folder, Producer, ProducerCallback, UnindexedConsumer, UnindexedProducer,
+    };
+    use rayon::iter::{plumbing, IndexedParallelIterator, ParallelIterator};
+    use std::cmp::min;
+    use std::marker::PhantomData;
+    use std::sync::Arc;
 
     pub struct ParRawIter<'a, T> {
         pub(super) buffer: &'a RawBuffer<T>,
@@ -319,6 +883,296 @@ mod rayon {
             folder.consume(slice)
         }
     }
+
+    /// One readable run within a segment: `len` initialized elements
+    /// starting at `ptr`, covering global element offsets
+    /// `base..base + len`.
+    struct Span<T> {
+        ptr: *mut T,
+        base: usize,
+        len: usize,
+    }
+
+    unsafe impl<T: Send> Send for Span<T> {}
+    unsafe impl<T: Send> Sync for Span<T> {}
+
+    /// Finds the element at global offset `index` among `spans`,
+    /// which are sorted and contiguous by `base`.
+    fn locate<'a, T>(spans: &[Span<T>], index: usize) -> &'a mut T {
+        let i = spans.partition_point(|span| span.base + span.len <= index);
+        let span = &spans[i];
+        unsafe { &mut *span.ptr.add(index - span.base) }
+    }
+
+    /// An [`IndexedParallelIterator`] over the elements of a
+    /// [`RawBuffer`], built by walking the segment list once up front
+    /// to record each segment's readable span. Unlike [`ParRawIter`],
+    /// which can only split on segment boundaries, this can split at
+    /// any element offset, so rayon can balance work evenly across
+    /// threads regardless of how unevenly sized the segments are, and
+    /// combinators like `zip`/`enumerate`/`collect_into_vec` work.
+    pub struct IndexedParRawIter<'a, T> {
+        spans: Arc<[Span<T>]>,
+        offset: usize,
+        len: usize,
+        marker: PhantomData<&'a mut T>,
+    }
+
+    unsafe impl<'a, T> Send for IndexedParRawIter<'a, T> where T: Send {}
+
+    impl<'a, T: Send> ParallelIterator for IndexedParRawIter<'a, T> {
+        type Item = &'a mut T;
+
+        fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where
+            C: UnindexedConsumer<Self::Item>,
+        {
+            plumbing::bridge(self, consumer)
+        }
+
+        fn opt_len(&self) -> Option<usize> {
+            Some(self.len)
+        }
+    }
+
+    impl<'a, T: Send> IndexedParallelIterator for IndexedParRawIter<'a, T> {
+        fn len(&self) -> usize {
+            self.len
+        }
+
+        fn drive<C>(self, consumer: C) -> C::Result
+        where
+            C: Consumer<Self::Item>,
+        {
+            plumbing::bridge(self, consumer)
+        }
+
+        fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where
+            CB: ProducerCallback<Self::Item>,
+        {
+            callback.callback(IndexedRawProducer {
+                spans: self.spans,
+                offset: self.offset,
+                len: self.len,
+                marker: PhantomData,
+            })
+        }
+    }
+
+    struct IndexedRawProducer<'a, T> {
+        spans: Arc<[Span<T>]>,
+        offset: usize,
+        len: usize,
+        marker: PhantomData<&'a mut T>,
+    }
+
+    unsafe impl<'a, T> Send for IndexedRawProducer<'a, T> where T: Send {}
+
+    impl<'a, T: Send> Producer for IndexedRawProducer<'a, T> {
+        type Item = &'a mut T;
+        type IntoIter = IndexedRawProducerIter<'a, T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            IndexedRawProducerIter {
+                spans: self.spans,
+                offset: self.offset,
+                remaining: self.len,
+                marker: PhantomData,
+            }
+        }
+
+        fn split_at(self, mid: usize) -> (Self, Self) {
+            (
+                Self {
+                    spans: Arc::clone(&self.spans),
+                    offset: self.offset,
+                    len: mid,
+                    marker: PhantomData,
+                },
+                Self {
+                    spans: self.spans,
+                    offset: self.offset + mid,
+                    len: self.len - mid,
+                    marker: PhantomData,
+                },
+            )
+        }
+    }
+
+    struct IndexedRawProducerIter<'a, T> {
+        spans: Arc<[Span<T>]>,
+        offset: usize,
+        remaining: usize,
+        marker: PhantomData<&'a mut T>,
+    }
+
+    impl<'a, T> Iterator for IndexedRawProducerIter<'a, T> {
+        type Item = &'a mut T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.remaining == 0 {
+                return None;
+            }
+            let item = locate(&self.spans, self.offset);
+            self.offset += 1;
+            self.remaining -= 1;
+            Some(item)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.remaining, Some(self.remaining))
+        }
+    }
+
+    impl<'a, T> ExactSizeIterator for IndexedRawProducerIter<'a, T> {}
+
+    impl<'a, T> DoubleEndedIterator for IndexedRawProducerIter<'a, T> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            if self.remaining == 0 {
+                return None;
+            }
+            self.remaining -= 1;
+            Some(locate(&self.spans, self.offset + self.remaining))
+        }
+    }
+
+    impl<T> RawBuffer<T> {
+        /// Returns an indexed parallel iterator over the buffer's
+        /// elements.
+        ///
+        /// This walks the segment list once to record each segment's
+        /// readable span before handing control to rayon, so splits can
+        /// land at any element offset rather than only at segment
+        /// boundaries (see [`IndexedParRawIter`]).
+        ///
+        /// # Safety
+        /// Neither push operations or other pop operations may not run in parallel with this function.
+        pub fn par_iter_indexed(&mut self) -> IndexedParRawIter<T> {
+            let mut spans = Vec::new();
+            let mut cumulative = 0;
+            let mut segment = *self.tail.get_mut();
+
+            while let Some(seg) = unsafe { segment.as_mut() } {
+                let start = min(*seg.back.get_mut(), seg.capacity);
+                let end = min(*seg.front.get_mut(), seg.capacity);
+
+                if end > start {
+                    let ptr = seg.array[start].get() as *mut T;
+                    spans.push(Span {
+                        ptr,
+                        base: cumulative,
+                        len: end - start,
+                    });
+                    cumulative += end - start;
+                }
+
+                segment = *seg.next.get_mut();
+            }
+
+            IndexedParRawIter {
+                spans: Arc::from(spans),
+                offset: 0,
+                len: cumulative,
+                marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "mpmc")]
+pub use self::handle::{Consumer, Producer};
+
+#[cfg(feature = "mpmc")]
+mod handle {
+    use super::RawBuffer;
+    use std::marker::PhantomData;
+    use std::sync::Arc;
+
+    /// A cloneable write handle produced by [`RawBuffer::split`].
+    ///
+    /// Any number of `Producer`s may push concurrently; they can never
+    /// alias the sole [`Consumer`]'s mutable drain, since that requires
+    /// a `Consumer` value, of which only one exists.
+    ///
+    /// The `PhantomData<T>` marker is load-bearing: without it, the
+    /// struct's only field is an `Arc<RawBuffer<T>>`, which is `Send`
+    /// and `Sync` for every `T` (it only ever touches `T` through atomics
+    /// and raw pointers), so `Producer<T>` would auto-derive `Send`/`Sync`
+    /// even for `T` that must never cross threads, e.g. `Rc<U>`. The
+    /// marker ties the derivation to `T: Send`/`T: Sync` instead.
+    pub struct Producer<T> {
+        pub(super) buffer: Arc<RawBuffer<T>>,
+        pub(super) marker: PhantomData<T>,
+    }
+
+    impl<T> Clone for Producer<T> {
+        fn clone(&self) -> Self {
+            Self {
+                buffer: Arc::clone(&self.buffer),
+                marker: PhantomData,
+            }
+        }
+    }
+
+    impl<T> Producer<T> {
+        pub fn push(&self, value: T) {
+            unsafe { self.buffer.push(value) };
+        }
+    }
+
+    impl<T: Copy> Producer<T> {
+        pub fn push_slice(&self, src: &[T]) {
+            unsafe { self.buffer.push_slice(src) };
+        }
+    }
+
+    /// The sole read handle produced by [`RawBuffer::split`].
+    ///
+    /// Deliberately not `Clone`: the buffer's single-consumer invariant
+    /// is enforced by construction, since only one `Consumer` is ever
+    /// handed out per buffer. Pops through
+    /// [`pop_concurrent`](RawBuffer::pop_concurrent) rather than the
+    /// exclusive-access `pop`, since a live `Producer` may be pushing at
+    /// the same time -- that's also why this is sound against a racing
+    /// `push`: `pop_concurrent`'s segment retirement never frees a
+    /// segment while a stale read could still be in flight on it (see
+    /// `RawBuffer::retired_head`). `iter`/`par_iter` are deliberately not
+    /// exposed here: both require that nothing else is concurrently
+    /// pushing, which a split buffer can never guarantee.
+    ///
+    /// See the [`Producer`] doc comment for why the `PhantomData<T>`
+    /// marker is required for a sound `Send` impl.
+    pub struct Consumer<T> {
+        pub(super) buffer: Arc<RawBuffer<T>>,
+        pub(super) marker: PhantomData<T>,
+    }
+
+    impl<T> Consumer<T> {
+        pub fn pop(&mut self) -> Option<T> {
+            unsafe { self.buffer.pop_concurrent() }
+        }
+    }
+
+    impl<T: Copy> Consumer<T> {
+        /// Pops at most `dst.len()` values into `dst`, returning how many
+        /// were written. Implemented as repeated [`pop`](Consumer::pop)
+        /// calls: unlike the exclusive-access `pop_slice`, there's no
+        /// bulk concurrent-safe path, only the per-element one.
+        pub fn pop_slice(&mut self, dst: &mut [T]) -> usize {
+            let mut popped = 0;
+            while popped < dst.len() {
+                match self.pop() {
+                    Some(value) => {
+                        dst[popped] = value;
+                        popped += 1;
+                    }
+                    None => break,
+                }
+            }
+            popped
+        }
+    }
 }
 
 #[cfg(test)]
@@ -344,4 +1198,299 @@ mod tests {
             assert_eq!(unsafe { buffer.pop() }, Some(i));
         }
     }
+
+    #[cfg(feature = "mpmc")]
+    #[test]
+    fn pop_concurrent_many_threads() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let buffer = Arc::new(RawBuffer::new());
+        for i in 0..8192 {
+            unsafe { buffer.push(i) };
+        }
+
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let buffer = Arc::clone(&buffer);
+            handles.push(thread::spawn(move || {
+                let mut popped = Vec::new();
+                while let Some(value) = unsafe { buffer.pop_concurrent() } {
+                    popped.push(value);
+                }
+                popped
+            }));
+        }
+
+        let mut all: Vec<_> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..8192).collect::<Vec<_>>());
+    }
+
+    // Unlike `pop_concurrent_many_threads`, this interleaves pushing and
+    // popping instead of pushing everything up front, so it actually
+    // exercises the case where a consumer catches up to `front` mid-flight
+    // and must retry its claim on `back` rather than stranding it.
+    #[cfg(feature = "mpmc")]
+    #[test]
+    fn pop_concurrent_interleaved_with_push() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let buffer = Arc::new(RawBuffer::new());
+        const ITEMS_PER_PRODUCER: i32 = 4096;
+        const PRODUCERS: i32 = 4;
+        const CONSUMERS: i32 = 4;
+
+        let mut handles = Vec::new();
+        for p in 0..PRODUCERS {
+            let buffer = Arc::clone(&buffer);
+            handles.push(thread::spawn(move || {
+                for i in 0..ITEMS_PER_PRODUCER {
+                    unsafe { buffer.push(p * ITEMS_PER_PRODUCER + i) };
+                }
+            }));
+        }
+
+        let popped = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for _ in 0..CONSUMERS {
+            let buffer = Arc::clone(&buffer);
+            let popped = Arc::clone(&popped);
+            handles.push(thread::spawn(move || {
+                let total = (PRODUCERS * ITEMS_PER_PRODUCER) as usize;
+                loop {
+                    if let Some(value) = unsafe { buffer.pop_concurrent() } {
+                        let mut popped = popped.lock().unwrap();
+                        popped.push(value);
+                        if popped.len() == total {
+                            return;
+                        }
+                    } else if popped.lock().unwrap().len() == total {
+                        return;
+                    } else {
+                        std::hint::spin_loop();
+                    }
+                }
+            }));
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut all = Arc::try_unwrap(popped).unwrap().into_inner().unwrap();
+        all.sort_unstable();
+        assert_eq!(
+            all,
+            (0..PRODUCERS * ITEMS_PER_PRODUCER).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_iter_indexed_preserves_order_across_uneven_segments() {
+        // Qualified as `::rayon` rather than `rayon`: `use super::*;`
+        // above brings this crate's own private `mod rayon` into scope
+        // under that name, which would otherwise shadow the external
+        // `rayon` crate.
+        use ::rayon::prelude::*;
+
+        let mut buffer = RawBuffer::new();
+
+        // Push/pop/push again so the live elements span several
+        // differently-sized segments with a partially-drained first one,
+        // exercising `locate`'s binary search across span boundaries.
+        for i in 0..100u32 {
+            unsafe { buffer.push(i) };
+        }
+        for _ in 0..30 {
+            unsafe { buffer.pop() };
+        }
+        for i in 100..300u32 {
+            unsafe { buffer.push(i) };
+        }
+
+        let collected: Vec<u32> = buffer.par_iter_indexed().map(|value| *value).collect();
+        assert_eq!(collected, (30..300).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_iter_indexed_splits_correctly_across_real_threads() {
+        use ::rayon::prelude::*;
+        use ::rayon::ThreadPoolBuilder;
+
+        let mut buffer = RawBuffer::new();
+        for i in 0..20_000u64 {
+            unsafe { buffer.push(i) };
+        }
+
+        // A pool with several threads forces `IndexedRawProducer::split_at`
+        // to actually run (a single-threaded pool would never split), so
+        // this checks that splitting at arbitrary element offsets -- not
+        // just segment boundaries -- produces the right total and that no
+        // element is visited twice.
+        let pool = ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+        let sum: u64 = pool.install(|| buffer.par_iter_indexed().map(|value| *value).sum());
+        assert_eq!(sum, (0..20_000u64).sum::<u64>());
+    }
+
+    #[test]
+    fn slice_push_pop() {
+        let mut buffer = RawBuffer::new();
+
+        let src: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+        unsafe { buffer.push_slice(&src) };
+
+        let mut dst = vec![0u8; src.len()];
+        let moved = unsafe { buffer.pop_slice(&mut dst) };
+
+        assert_eq!(moved, src.len());
+        assert_eq!(dst, src);
+        assert_eq!(unsafe { buffer.pop_slice(&mut [0u8; 1]) }, 0);
+    }
+
+    #[test]
+    fn io_read_write() {
+        use std::io::{Read, Write};
+
+        let mut buffer = RawBuffer::new();
+
+        let written = buffer.write(b"hello world").unwrap();
+        assert_eq!(written, 11);
+
+        let mut out = [0u8; 32];
+        let read = buffer.read(&mut out).unwrap();
+        assert_eq!(&out[..read], b"hello world");
+        assert_eq!(buffer.read(&mut out).unwrap(), 0);
+    }
+
+    #[test]
+    fn builder_small_segments() {
+        let mut buffer: RawBuffer<u32> = RawBufferBuilder::new()
+            .starting_capacity(4)
+            .max_capacity(8)
+            .growth_factor(2)
+            .max_free_segments(1)
+            .build();
+
+        // Drive enough churn to force growth up to `max_capacity` and
+        // recycle several segments through the bounded free list.
+        for round in 0..50 {
+            for i in 0..20 {
+                unsafe { buffer.push(round * 20 + i) };
+            }
+            for i in 0..20 {
+                assert_eq!(unsafe { buffer.pop() }, Some(round * 20 + i));
+            }
+        }
+        assert_eq!(unsafe { buffer.pop() }, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "starting_capacity must be greater than 0")]
+    fn builder_rejects_zero_starting_capacity() {
+        let _: RawBuffer<u32> = RawBufferBuilder::new().starting_capacity(0).build();
+    }
+
+    #[test]
+    #[should_panic(expected = "growth_factor must be at least 1")]
+    fn builder_rejects_zero_growth_factor() {
+        let _: RawBuffer<u32> = RawBufferBuilder::new().growth_factor(0).build();
+    }
+
+    #[test]
+    #[should_panic(expected = "max_capacity must be greater than 0")]
+    fn builder_rejects_zero_max_capacity() {
+        let _: RawBuffer<u32> = RawBufferBuilder::new()
+            .starting_capacity(4)
+            .max_capacity(0)
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "max_capacity must be at least starting_capacity")]
+    fn builder_rejects_max_capacity_below_starting_capacity() {
+        let _: RawBuffer<u32> = RawBufferBuilder::new()
+            .starting_capacity(64)
+            .max_capacity(8)
+            .build();
+    }
+
+    #[cfg(feature = "mpmc")]
+    #[test]
+    fn split_producer_consumer() {
+        use std::thread;
+
+        let (producer, mut consumer) = RawBuffer::new().split();
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let producer = producer.clone();
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        producer.push(t * 1000 + i);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        drop(producer);
+
+        let mut popped = Vec::new();
+        while let Some(value) = consumer.pop() {
+            popped.push(value);
+        }
+        popped.sort_unstable();
+        assert_eq!(popped, (0..4000).collect::<Vec<_>>());
+    }
+
+    // Producer and Consumer are meant to run concurrently, unlike
+    // `split_producer_consumer` above which joins every producer before
+    // popping anything. This exercises `Consumer::pop` actually racing a
+    // live `Producer`.
+    #[cfg(feature = "mpmc")]
+    #[test]
+    fn split_producer_consumer_interleaved() {
+        use std::thread;
+
+        const PRODUCERS: i32 = 4;
+        const ITEMS_PER_PRODUCER: i32 = 4096;
+        const TOTAL: i32 = PRODUCERS * ITEMS_PER_PRODUCER;
+
+        let (producer, mut consumer) = RawBuffer::new().split();
+
+        let producer_handles: Vec<_> = (0..PRODUCERS)
+            .map(|t| {
+                let producer = producer.clone();
+                thread::spawn(move || {
+                    for i in 0..ITEMS_PER_PRODUCER {
+                        producer.push(t * ITEMS_PER_PRODUCER + i);
+                    }
+                })
+            })
+            .collect();
+        drop(producer);
+
+        let mut popped = Vec::new();
+        while popped.len() < TOTAL as usize {
+            if let Some(value) = consumer.pop() {
+                popped.push(value);
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+
+        for handle in producer_handles {
+            handle.join().unwrap();
+        }
+
+        popped.sort_unstable();
+        assert_eq!(popped, (0..TOTAL).collect::<Vec<_>>());
+    }
 }